@@ -1,15 +1,141 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+pub mod serde_support;
 
 const DEFAULT_SEPARATOR: &str = ": ";
 const DEFAULT_NEWLINE: &str = "\n";
+const DEFAULT_FOLD_JOIN: &str = "\n";
+const DEFAULT_FOLD_INDENT: &str = "    ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    #[default]
+    Plain,
+    #[cfg(feature = "value-encoding")]
+    Base64,
+    #[cfg(feature = "value-encoding")]
+    Base32,
+}
+
+impl Encoding {
+    fn encode(self, value: &str) -> String {
+        match self {
+            Encoding::Plain => value.to_owned(),
+            #[cfg(feature = "value-encoding")]
+            Encoding::Base64 => data_encoding::BASE64.encode(value.as_bytes()),
+            #[cfg(feature = "value-encoding")]
+            Encoding::Base32 => data_encoding::BASE32.encode(value.as_bytes()),
+        }
+    }
+
+    #[cfg(feature = "value-encoding")]
+    fn decode(self, value: &str) -> Result<Vec<u8>, data_encoding::DecodeError> {
+        match self {
+            Encoding::Plain => Ok(value.as_bytes().to_vec()),
+            Encoding::Base64 => data_encoding::BASE64.decode(value.as_bytes()),
+            Encoding::Base32 => data_encoding::BASE32.decode(value.as_bytes()),
+        }
+    }
+}
+
+fn escape_fragment(s: &str, separator: &str, newline: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut i = 0;
+
+    while i < s.len() {
+        let rest = &s[i..];
+
+        if rest.starts_with('\\') {
+            out.push_str("\\\\");
+            i += 1;
+        } else if !newline.is_empty() && rest.starts_with(newline) {
+            out.push_str("\\n");
+            i += newline.len();
+        } else if !separator.is_empty() && rest.starts_with(separator) {
+            out.push('\\');
+            out.push_str(separator);
+            i += separator.len();
+        } else {
+            let ch = rest.chars().next().unwrap();
+            out.push(ch);
+            i += ch.len_utf8();
+        }
+    }
+
+    out
+}
+
+fn split_unescaped<'a>(line: &'a str, separator: &str) -> Option<(&'a str, &'a str)> {
+    if separator.is_empty() {
+        return None;
+    }
+
+    let mut search_from = 0;
+
+    while let Some(rel) = line[search_from..].find(separator) {
+        let idx = search_from + rel;
+
+        let mut backslashes = 0;
+        let mut j = idx;
+        while j > 0 && line.as_bytes()[j - 1] == b'\\' {
+            backslashes += 1;
+            j -= 1;
+        }
+
+        if backslashes % 2 == 0 {
+            return Some((&line[..idx], &line[idx + separator.len()..]));
+        }
+
+        search_from = idx + 1;
+    }
+
+    None
+}
+
+fn try_unescape_fragment(s: &str, separator: &str, newline: &str) -> Result<String, ()> {
+    let mut out = String::with_capacity(s.len());
+    let mut i = 0;
+
+    while i < s.len() {
+        let rest = &s[i..];
+
+        if let Some(stripped) = rest.strip_prefix('\\') {
+            if stripped.starts_with('\\') {
+                out.push('\\');
+                i += 2;
+            } else if stripped.starts_with('n') {
+                out.push_str(newline);
+                i += 2;
+            } else if !separator.is_empty() && stripped.starts_with(separator) {
+                out.push_str(separator);
+                i += 1 + separator.len();
+            } else {
+                return Err(());
+            }
+        } else {
+            let ch = rest.chars().next().unwrap();
+            out.push(ch);
+            i += ch.len_utf8();
+        }
+    }
+
+    Ok(out)
+}
 
-pub struct Serializer<'a, PairIter, ExtraIter>
+pub struct Serializer<'a, PairIter, ExtraIter = core::slice::Iter<'a, &'a str>>
 where
     PairIter: Iterator<Item = &'a (&'a str, &'a str)>,
     ExtraIter: Iterator<Item = &'a &'a str>,
 {
     separator: &'a str,
     newline: &'a str,
+    escape: bool,
+    fold: bool,
+    fold_join: &'a str,
+    fold_indent: &'a str,
+    value_encoding: Encoding,
     pairs: Option<PairIter>,
     extra_lines: Option<ExtraIter>,
 }
@@ -23,6 +149,11 @@ where
         Self {
             separator: DEFAULT_SEPARATOR,
             newline: DEFAULT_NEWLINE,
+            escape: false,
+            fold: false,
+            fold_join: DEFAULT_FOLD_JOIN,
+            fold_indent: DEFAULT_FOLD_INDENT,
+            value_encoding: Encoding::Plain,
             pairs: None,
             extra_lines: None,
         }
@@ -38,6 +169,31 @@ where
         self
     }
 
+    pub fn escape(mut self, escape: bool) -> Self {
+        self.escape = escape;
+        self
+    }
+
+    pub fn fold(mut self, fold: bool) -> Self {
+        self.fold = fold;
+        self
+    }
+
+    pub fn fold_join(mut self, fold_join: &'a str) -> Self {
+        self.fold_join = fold_join;
+        self
+    }
+
+    pub fn fold_indent(mut self, fold_indent: &'a str) -> Self {
+        self.fold_indent = fold_indent;
+        self
+    }
+
+    pub fn value_encoding(mut self, value_encoding: Encoding) -> Self {
+        self.value_encoding = value_encoding;
+        self
+    }
+
     pub fn pairs(mut self, pairs: PairIter) -> Self {
         self.pairs = Some(pairs);
         self
@@ -53,9 +209,36 @@ where
 
         if let Some(pairs) = self.pairs {
             for (k, v) in pairs {
-                out.push_str(k);
-                out.push_str(self.separator);
-                out.push_str(v);
+                let encoded;
+                let v: &str = if self.value_encoding == Encoding::Plain {
+                    v
+                } else {
+                    encoded = self.value_encoding.encode(v);
+                    &encoded
+                };
+
+                if self.escape {
+                    out.push_str(&escape_fragment(k, self.separator, self.newline));
+                    out.push_str(self.separator);
+                    out.push_str(&escape_fragment(v, self.separator, self.newline));
+                } else if self.fold {
+                    out.push_str(k);
+                    out.push_str(self.separator);
+
+                    let mut segments = v.split(self.fold_join);
+                    if let Some(first) = segments.next() {
+                        out.push_str(first);
+                    }
+                    for segment in segments {
+                        out.push_str(self.newline);
+                        out.push_str(self.fold_indent);
+                        out.push_str(segment);
+                    }
+                } else {
+                    out.push_str(k);
+                    out.push_str(self.separator);
+                    out.push_str(v);
+                }
                 out.push_str(self.newline);
             }
         }
@@ -71,10 +254,9 @@ where
     }
 }
 
-pub fn serializer<'a, PairIter, ExtraIter>() -> Serializer<'a, PairIter, ExtraIter>
+pub fn serializer<'a, PairIter>() -> Serializer<'a, PairIter>
 where
     PairIter: Iterator<Item = &'a (&'a str, &'a str)>,
-    ExtraIter: Iterator<Item = &'a &'a str>,
 {
     Serializer::new()
 }
@@ -83,9 +265,7 @@ pub fn to_string<'a, PairIter>(iterable: PairIter) -> String
 where
     PairIter: Iterator<Item = &'a (&'a str, &'a str)>,
 {
-    serializer::<'a, PairIter, core::slice::Iter<&'a str>>()
-        .pairs(iterable)
-        .serialize()
+    serializer().pairs(iterable).serialize()
 }
 
 pub fn serialize<'a, PairIter, ExtraIter>(pairs_iter: PairIter, extra_iter: ExtraIter) -> String
@@ -93,7 +273,7 @@ where
     PairIter: Iterator<Item = &'a (&'a str, &'a str)>,
     ExtraIter: Iterator<Item = &'a &'a str>,
 {
-    serializer()
+    Serializer::<'a, PairIter, ExtraIter>::new()
         .pairs(pairs_iter)
         .extra_lines(extra_iter)
         .serialize()
@@ -102,6 +282,10 @@ where
 pub struct Deserializer<'a, 'b> {
     separator: &'a str,
     newline: &'a str,
+    escape: bool,
+    fold: bool,
+    fold_join: &'a str,
+    value_encoding: Encoding,
     keys: &'b [&'b str],
 }
 
@@ -110,6 +294,10 @@ impl<'a, 'b> Deserializer<'a, 'b> {
         Self {
             separator: DEFAULT_SEPARATOR,
             newline: DEFAULT_NEWLINE,
+            escape: false,
+            fold: false,
+            fold_join: DEFAULT_FOLD_JOIN,
+            value_encoding: Encoding::Plain,
             keys: &[],
         }
     }
@@ -124,34 +312,196 @@ impl<'a, 'b> Deserializer<'a, 'b> {
         self
     }
 
+    pub fn escape(mut self, escape: bool) -> Self {
+        self.escape = escape;
+        self
+    }
+
+    pub fn fold(mut self, fold: bool) -> Self {
+        self.fold = fold;
+        self
+    }
+
+    pub fn fold_join(mut self, fold_join: &'a str) -> Self {
+        self.fold_join = fold_join;
+        self
+    }
+
+    pub fn value_encoding(mut self, value_encoding: Encoding) -> Self {
+        self.value_encoding = value_encoding;
+        self
+    }
+
     pub fn keys(mut self, keys: &'b [&'b str]) -> Self {
         self.keys = keys;
         self
     }
 
+    fn split_line(&self, line: &'a str) -> Option<(&'a str, &'a str)> {
+        if self.escape {
+            split_unescaped(line, self.separator)
+        } else {
+            // TODO: Use line.split_once() when stable
+
+            let splits: Vec<_> = line.splitn(2, self.separator).collect();
+            if splits.len() == 2 {
+                Some((splits[0], splits[1]))
+            } else {
+                None
+            }
+        }
+    }
+
     pub fn deserialize(self, source: &'a str) -> DeserializeData<'a> {
         let mut pairs = Vec::new();
         let mut extra_lines = Vec::new();
 
         for line in source.lines() {
-            // TODO: Use line.split_once() when stable
+            match self.split_line(line) {
+                Some((key, value)) if self.keys.contains(&key) => pairs.push((key, value)),
+                _ => extra_lines.push(line),
+            }
+        }
 
-            let splits: Vec<_> = line.splitn(2, self.separator).collect();
+        DeserializeData {
+            pairs,
+            extra_lines,
+            value_encoding: self.value_encoding,
+        }
+    }
 
-            if splits.len() == 2 && self.keys.contains(&splits[0]) {
-                pairs.push((splits[0], splits[1]));
-            } else {
-                extra_lines.push(line);
+    pub fn deserialize_strict(self, source: &'a str) -> Result<DeserializeData<'a>, DeserializeError> {
+        let mut pairs = Vec::new();
+        let mut seen = HashSet::new();
+
+        for (idx, line) in source.lines().enumerate() {
+            let line_number = idx + 1;
+
+            let (key, value) = self.split_line(line).ok_or_else(|| DeserializeError::MissingSeparator {
+                line: line_number,
+                text: line.to_owned(),
+            })?;
+
+            if !self.keys.contains(&key) {
+                return Err(DeserializeError::UnknownKey {
+                    line: line_number,
+                    text: line.to_owned(),
+                });
+            }
+
+            if !seen.insert(key) {
+                return Err(DeserializeError::DuplicateKey {
+                    line: line_number,
+                    text: line.to_owned(),
+                });
+            }
+
+            if self.escape {
+                try_unescape_fragment(value, self.separator, self.newline).map_err(|_| {
+                    DeserializeError::TrailingBackslash {
+                        line: line_number,
+                        text: line.to_owned(),
+                    }
+                })?;
+            }
+
+            #[cfg(feature = "value-encoding")]
+            if self.value_encoding != Encoding::Plain {
+                self.value_encoding
+                    .decode(value)
+                    .map_err(|_| DeserializeError::InvalidEncoding {
+                        line: line_number,
+                        text: line.to_owned(),
+                    })?;
+            }
+
+            pairs.push((key, value));
+        }
+
+        if let Some(&missing) = self.keys.iter().find(|key| !seen.contains(*key)) {
+            return Err(DeserializeError::MissingRequiredKey {
+                key: missing.to_owned(),
+            });
+        }
+
+        Ok(DeserializeData {
+            pairs,
+            extra_lines: Vec::new(),
+            value_encoding: self.value_encoding,
+        })
+    }
+
+    pub fn deserialize_folded(self, source: &'a str) -> FoldedDeserializeData<'a> {
+        let mut pairs: Vec<(&'a str, String)> = Vec::new();
+        let mut extra_lines = Vec::new();
+
+        for line in source.lines() {
+            let is_continuation = self.fold
+                && !pairs.is_empty()
+                && (line.starts_with(' ') || line.starts_with('\t'));
+
+            if is_continuation {
+                let stripped = line.trim_start_matches([' ', '\t']);
+                let (_, value) = pairs.last_mut().expect("checked by is_continuation");
+                value.push_str(self.fold_join);
+                value.push_str(stripped);
+                continue;
+            }
+
+            match self.split_line(line) {
+                Some((key, value)) if self.keys.contains(&key) => {
+                    pairs.push((key, value.to_owned()))
+                }
+                _ => extra_lines.push(line),
             }
         }
 
-        DeserializeData { pairs, extra_lines }
+        FoldedDeserializeData { pairs, extra_lines }
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeserializeError {
+    MissingSeparator { line: usize, text: String },
+    UnknownKey { line: usize, text: String },
+    DuplicateKey { line: usize, text: String },
+    MissingRequiredKey { key: String },
+    TrailingBackslash { line: usize, text: String },
+    InvalidEncoding { line: usize, text: String },
+}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeserializeError::MissingSeparator { line, text } => {
+                write!(f, "line {line}: missing separator: {text:?}")
+            }
+            DeserializeError::UnknownKey { line, text } => {
+                write!(f, "line {line}: unknown key: {text:?}")
+            }
+            DeserializeError::DuplicateKey { line, text } => {
+                write!(f, "line {line}: duplicate key: {text:?}")
+            }
+            DeserializeError::MissingRequiredKey { key } => {
+                write!(f, "missing required key: {key:?}")
+            }
+            DeserializeError::TrailingBackslash { line, text } => {
+                write!(f, "line {line}: trailing backslash: {text:?}")
+            }
+            DeserializeError::InvalidEncoding { line, text } => {
+                write!(f, "line {line}: invalid encoding: {text:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DeserializeError {}
+
+#[derive(Debug)]
 pub struct DeserializeData<'a> {
     pub pairs: Vec<(&'a str, &'a str)>,
     pub extra_lines: Vec<&'a str>,
+    pub value_encoding: Encoding,
 }
 
 impl<'a> DeserializeData<'a> {
@@ -176,6 +526,76 @@ impl<'a> DeserializeData<'a> {
             .map(|line| (*line).to_owned())
             .collect()
     }
+
+    pub fn unescape_pairs(&self, separator: &str, newline: &str) -> Vec<(String, String)> {
+        self.pairs
+            .iter()
+            .map(|(k, v)| {
+                let key = try_unescape_fragment(k, separator, newline).unwrap_or_else(|_| (*k).to_owned());
+                let value = try_unescape_fragment(v, separator, newline).unwrap_or_else(|_| (*v).to_owned());
+                (key, value)
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "value-encoding")]
+    pub fn pairs_bytes(&self) -> Result<Vec<(&'a str, Vec<u8>)>, data_encoding::DecodeError> {
+        self.pairs
+            .iter()
+            .map(|(k, v)| self.value_encoding.decode(v).map(|bytes| (*k, bytes)))
+            .collect()
+    }
+
+    pub fn pairs_ordered(&self) -> Vec<(&'a str, &'a str)> {
+        self.pairs.clone()
+    }
+
+    #[cfg(not(feature = "indexmap"))]
+    pub fn pairs_multimap(&self) -> Vec<(&'a str, Vec<&'a str>)> {
+        let mut groups: Vec<(&'a str, Vec<&'a str>)> = Vec::new();
+        let mut index = HashMap::new();
+
+        for &(k, v) in &self.pairs {
+            match index.get(&k) {
+                Some(&i) => {
+                    let (_, values): &mut (&'a str, Vec<&'a str>) = &mut groups[i];
+                    values.push(v);
+                }
+                None => {
+                    index.insert(k, groups.len());
+                    groups.push((k, vec![v]));
+                }
+            }
+        }
+
+        groups
+    }
+
+    #[cfg(feature = "indexmap")]
+    pub fn pairs_multimap(&self) -> Vec<(&'a str, Vec<&'a str>)> {
+        let mut map: indexmap::IndexMap<&'a str, Vec<&'a str>> = indexmap::IndexMap::new();
+
+        for &(k, v) in &self.pairs {
+            map.entry(k).or_default().push(v);
+        }
+
+        map.into_iter().collect()
+    }
+}
+
+pub struct FoldedDeserializeData<'a> {
+    pub pairs: Vec<(&'a str, String)>,
+    pub extra_lines: Vec<&'a str>,
+}
+
+impl<'a> FoldedDeserializeData<'a> {
+    pub fn pairs_hashmap(&self) -> HashMap<&'a str, String> {
+        self.pairs.iter().map(|(k, v)| (*k, v.clone())).collect()
+    }
+
+    pub fn extra_lines_vec(&self) -> Vec<&'a str> {
+        self.extra_lines.clone()
+    }
 }
 
 pub fn deserializer<'a, 'b>() -> Deserializer<'a, 'b> {
@@ -186,7 +606,9 @@ pub fn parse<'a, 'b>(
     keys: &'b [&'b str],
     source: &'a str,
 ) -> (Vec<(&'a str, &'a str)>, Vec<&'a str>) {
-    let DeserializeData { pairs, extra_lines } = Deserializer::new().keys(keys).deserialize(source);
+    let DeserializeData {
+        pairs, extra_lines, ..
+    } = Deserializer::new().keys(keys).deserialize(source);
 
     (pairs, extra_lines)
 }
@@ -279,4 +701,292 @@ mod tests {
         assert_eq!(pairs, vec![("foo", "bar"), ("baz", "123")]);
         assert_eq!(extra_lines, vec!["extra=lines", "and stuff"]);
     }
+
+    #[test]
+    fn test_deserialize_strict_ok() {
+        let source = "\
+            foo: bar\n\
+            baz: 123\n\
+        ";
+
+        let data = deserializer()
+            .keys(&["foo", "baz"])
+            .deserialize_strict(source)
+            .unwrap();
+
+        assert_eq!(data.pairs, vec![("foo", "bar"), ("baz", "123")]);
+        assert_eq!(data.extra_lines, Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_deserialize_strict_missing_separator() {
+        let source = "foo: bar\nnot a pair\n";
+
+        let err = deserializer()
+            .keys(&["foo"])
+            .deserialize_strict(source)
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            DeserializeError::MissingSeparator {
+                line: 2,
+                text: "not a pair".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_strict_unknown_key() {
+        let source = "foo: bar\nbaz: 123\n";
+
+        let err = deserializer()
+            .keys(&["foo"])
+            .deserialize_strict(source)
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            DeserializeError::UnknownKey {
+                line: 2,
+                text: "baz: 123".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_strict_duplicate_key() {
+        let source = "foo: bar\nfoo: baz\n";
+
+        let err = deserializer()
+            .keys(&["foo"])
+            .deserialize_strict(source)
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            DeserializeError::DuplicateKey {
+                line: 2,
+                text: "foo: baz".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_strict_missing_required_key() {
+        let source = "foo: bar\n";
+
+        let err = deserializer()
+            .keys(&["foo", "baz"])
+            .deserialize_strict(source)
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            DeserializeError::MissingRequiredKey {
+                key: "baz".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_serialize_escape() {
+        let ser_string = serializer()
+            .escape(true)
+            .pairs([("foo", "bar: baz\nqux"), ("backslash", "a\\b")].iter())
+            .serialize();
+
+        let expected = "foo: bar\\: baz\\nqux\nbackslash: a\\\\b\n";
+
+        assert_eq!(ser_string, expected);
+    }
+
+    #[test]
+    fn test_deserialize_escape_round_trip() {
+        let pairs = [("foo", "bar: baz\nqux"), ("backslash", "a\\b")];
+        let ser_string = serializer().escape(true).pairs(pairs.iter()).serialize();
+
+        let data = deserializer()
+            .escape(true)
+            .keys(&["foo", "backslash"])
+            .deserialize(&ser_string);
+
+        let unescaped = data.unescape_pairs(DEFAULT_SEPARATOR, DEFAULT_NEWLINE);
+
+        assert_eq!(
+            unescaped,
+            vec![
+                ("foo".to_owned(), "bar: baz\nqux".to_owned()),
+                ("backslash".to_owned(), "a\\b".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_strict_escape_trailing_backslash() {
+        let source = "foo: bar\\";
+
+        let err = deserializer()
+            .escape(true)
+            .keys(&["foo"])
+            .deserialize_strict(source)
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            DeserializeError::TrailingBackslash {
+                line: 1,
+                text: "foo: bar\\".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_serialize_fold() {
+        let ser_string = serializer()
+            .fold(true)
+            .pairs([("description", "first line\nsecond line\nthird line")].iter())
+            .serialize();
+
+        let expected = "description: first line\n    second line\n    third line\n";
+
+        assert_eq!(ser_string, expected);
+    }
+
+    #[test]
+    fn test_deserialize_fold() {
+        let source = "description: first line\n    second line\n    third line\nfoo: bar\n# a comment\n";
+
+        let data = deserializer()
+            .fold(true)
+            .keys(&["description", "foo"])
+            .deserialize_folded(source);
+
+        assert_eq!(
+            data.pairs,
+            vec![
+                ("description", "first line\nsecond line\nthird line".to_owned()),
+                ("foo", "bar".to_owned()),
+            ]
+        );
+        assert_eq!(data.extra_lines, vec!["# a comment"]);
+    }
+
+    #[test]
+    fn test_deserialize_fold_round_trip() {
+        let pairs = [("description", "first line\nsecond line")];
+        let ser_string = serializer().fold(true).pairs(pairs.iter()).serialize();
+
+        let data = deserializer()
+            .fold(true)
+            .keys(&["description"])
+            .deserialize_folded(&ser_string);
+
+        assert_eq!(
+            data.pairs,
+            vec![("description", "first line\nsecond line".to_owned())]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_fold_continuation_before_any_pair() {
+        let source = "  leading continuation\nfoo: bar\n";
+
+        let data = deserializer()
+            .fold(true)
+            .keys(&["foo"])
+            .deserialize_folded(source);
+
+        assert_eq!(data.pairs, vec![("foo", "bar".to_owned())]);
+        assert_eq!(data.extra_lines, vec!["  leading continuation"]);
+    }
+
+    #[test]
+    #[cfg(feature = "value-encoding")]
+    fn test_serialize_value_encoding_base64() {
+        let ser_string = serializer()
+            .value_encoding(Encoding::Base64)
+            .pairs([("blob", "hi")].iter())
+            .serialize();
+
+        assert_eq!(ser_string, "blob: aGk=\n");
+    }
+
+    #[test]
+    #[cfg(feature = "value-encoding")]
+    fn test_deserialize_value_encoding_round_trip() {
+        let pairs = [("blob", "hi")];
+        let ser_string = serializer()
+            .value_encoding(Encoding::Base64)
+            .pairs(pairs.iter())
+            .serialize();
+
+        let data = deserializer()
+            .value_encoding(Encoding::Base64)
+            .keys(&["blob"])
+            .deserialize(&ser_string);
+
+        let bytes = data.pairs_bytes().unwrap();
+
+        assert_eq!(bytes, vec![("blob", b"hi".to_vec())]);
+    }
+
+    #[test]
+    #[cfg(feature = "value-encoding")]
+    fn test_deserialize_strict_invalid_encoding() {
+        let source = "blob: not valid base64!!\n";
+
+        let err = deserializer()
+            .value_encoding(Encoding::Base64)
+            .keys(&["blob"])
+            .deserialize_strict(source)
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            DeserializeError::InvalidEncoding {
+                line: 1,
+                text: "blob: not valid base64!!".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_pairs_ordered() {
+        let source = "foo: 1\nbar: 2\nbaz: 3\n";
+
+        let data = deserializer().keys(&["foo", "bar", "baz"]).deserialize(source);
+
+        assert_eq!(
+            data.pairs_ordered(),
+            vec![("foo", "1"), ("bar", "2"), ("baz", "3")]
+        );
+    }
+
+    #[test]
+    fn test_pairs_multimap_preserves_order_and_duplicates() {
+        let source = "tag: a\nname: foo\ntag: b\ntag: c\nname: bar\n";
+
+        let data = deserializer().keys(&["tag", "name"]).deserialize(source);
+
+        assert_eq!(
+            data.pairs_multimap(),
+            vec![
+                ("tag", vec!["a", "b", "c"]),
+                ("name", vec!["foo", "bar"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pairs_multimap_single_values() {
+        let source = "foo: 1\nbar: 2\n";
+
+        let data = deserializer().keys(&["foo", "bar"]).deserialize(source);
+
+        assert_eq!(
+            data.pairs_multimap(),
+            vec![("foo", vec!["1"]), ("bar", vec!["2"])]
+        );
+    }
 }
@@ -0,0 +1,938 @@
+use std::fmt;
+
+use serde::de::{self, Visitor};
+use serde::ser;
+
+use crate::{DeserializeData, DEFAULT_NEWLINE, DEFAULT_SEPARATOR};
+
+#[derive(Clone, Copy)]
+pub struct Options<'a> {
+    pub separator: &'a str,
+    pub newline: &'a str,
+}
+
+impl<'a> Default for Options<'a> {
+    fn default() -> Self {
+        Self {
+            separator: DEFAULT_SEPARATOR,
+            newline: DEFAULT_NEWLINE,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Message(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Message(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+
+pub fn to_string<T: ser::Serialize>(value: &T) -> Result<String, Error> {
+    to_string_with_options(value, Options::default())
+}
+
+pub fn to_string_with_options<T: ser::Serialize>(
+    value: &T,
+    options: Options,
+) -> Result<String, Error> {
+    value.serialize(Serializer::new(options))
+}
+
+pub fn from_str<'de, T: de::Deserialize<'de>>(source: &'de str) -> Result<T, Error> {
+    from_str_with_options(source, Options::default())
+}
+
+pub fn from_str_with_options<'de, T: de::Deserialize<'de>>(
+    source: &'de str,
+    options: Options<'de>,
+) -> Result<T, Error> {
+    T::deserialize(Deserializer { source, options })
+}
+
+pub struct Serializer<'a> {
+    options: Options<'a>,
+    pairs: Vec<(String, String)>,
+    extra_lines: Vec<String>,
+}
+
+impl<'a> Serializer<'a> {
+    pub fn new(options: Options<'a>) -> Self {
+        Self {
+            options,
+            pairs: Vec::new(),
+            extra_lines: Vec::new(),
+        }
+    }
+
+    fn finish(self) -> String {
+        let pairs: Vec<(&str, &str)> = self
+            .pairs
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        let extra_lines: Vec<&str> = self.extra_lines.iter().map(|s| s.as_str()).collect();
+
+        crate::serializer()
+            .separator(self.options.separator)
+            .newline(self.options.newline)
+            .pairs(pairs.iter())
+            .extra_lines(extra_lines.iter())
+            .serialize()
+    }
+}
+
+macro_rules! unsupported_scalar {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method(self, _v: $ty) -> Result<Self::Ok, Self::Error> {
+                Err(Error::custom("top-level value must be a struct or map"))
+            }
+        )*
+    };
+}
+
+impl<'a> ser::Serializer for Serializer<'a> {
+    type Ok = String;
+    type Error = Error;
+    type SerializeSeq = ser::Impossible<String, Error>;
+    type SerializeTuple = ser::Impossible<String, Error>;
+    type SerializeTupleStruct = ser::Impossible<String, Error>;
+    type SerializeTupleVariant = ser::Impossible<String, Error>;
+    type SerializeMap = MapSerializer<'a>;
+    type SerializeStruct = StructSerializer<'a>;
+    type SerializeStructVariant = ser::Impossible<String, Error>;
+
+    unsupported_scalar! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8]),
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("top-level value must be a struct or map"))
+    }
+
+    fn serialize_some<T: ?Sized + ser::Serialize>(
+        self,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("top-level value must be a struct or map"))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("top-level value must be a struct or map"))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("top-level value must be a struct or map"))
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + ser::Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + ser::Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("top-level value must be a struct or map"))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(Error::custom("top-level value must be a struct or map"))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(Error::custom("top-level value must be a struct or map"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(Error::custom("top-level value must be a struct or map"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(Error::custom("top-level value must be a struct or map"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(MapSerializer {
+            ser: self,
+            pending_key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(StructSerializer { ser: self })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(Error::custom("top-level value must be a struct or map"))
+    }
+}
+
+pub struct StructSerializer<'a> {
+    ser: Serializer<'a>,
+}
+
+impl<'a> ser::SerializeStruct for StructSerializer<'a> {
+    type Ok = String;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + ser::Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        if key == "extra_lines" {
+            let lines = value.serialize(ExtraLinesSerializer)?;
+            self.ser.extra_lines.extend(lines);
+        } else {
+            let value = value.serialize(ScalarSerializer)?;
+            self.ser.pairs.push((key.to_owned(), value));
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.ser.finish())
+    }
+}
+
+pub struct MapSerializer<'a> {
+    ser: Serializer<'a>,
+    pending_key: Option<String>,
+}
+
+impl<'a> ser::SerializeMap for MapSerializer<'a> {
+    type Ok = String;
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized + ser::Serialize>(&mut self, key: &T) -> Result<(), Self::Error> {
+        self.pending_key = Some(key.serialize(ScalarSerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + ser::Serialize>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        let key = self
+            .pending_key
+            .take()
+            .ok_or_else(|| Error::custom("serialize_value called before serialize_key"))?;
+
+        if key == "extra_lines" {
+            let lines = value.serialize(ExtraLinesSerializer)?;
+            self.ser.extra_lines.extend(lines);
+        } else {
+            let value = value.serialize(ScalarSerializer)?;
+            self.ser.pairs.push((key, value));
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.ser.finish())
+    }
+}
+
+struct ScalarSerializer;
+
+macro_rules! scalar_to_string {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method(self, v: $ty) -> Result<Self::Ok, Self::Error> {
+                Ok(v.to_string())
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for ScalarSerializer {
+    type Ok = String;
+    type Error = Error;
+    type SerializeSeq = ser::Impossible<String, Error>;
+    type SerializeTuple = ser::Impossible<String, Error>;
+    type SerializeTupleStruct = ser::Impossible<String, Error>;
+    type SerializeTupleVariant = ser::Impossible<String, Error>;
+    type SerializeMap = ser::Impossible<String, Error>;
+    type SerializeStruct = ser::Impossible<String, Error>;
+    type SerializeStructVariant = ser::Impossible<String, Error>;
+
+    scalar_to_string! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("byte values are not supported as scalar field values"))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(String::new())
+    }
+
+    fn serialize_some<T: ?Sized + ser::Serialize>(
+        self,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(String::new())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(String::new())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Ok(variant.to_owned())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + ser::Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + ser::Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("newtype variants are not supported as scalar field values"))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(Error::custom("sequences are not supported as scalar field values"))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(Error::custom("tuples are not supported as scalar field values"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(Error::custom("tuples are not supported as scalar field values"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(Error::custom("tuple variants are not supported as scalar field values"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(Error::custom("maps are not supported as scalar field values"))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(Error::custom("nested structs are not supported as scalar field values"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(Error::custom("struct variants are not supported as scalar field values"))
+    }
+}
+
+struct ExtraLinesSerializer;
+
+impl ser::Serializer for ExtraLinesSerializer {
+    type Ok = Vec<String>;
+    type Error = Error;
+    type SerializeSeq = ExtraLinesSeq;
+    type SerializeTuple = ser::Impossible<Vec<String>, Error>;
+    type SerializeTupleStruct = ser::Impossible<Vec<String>, Error>;
+    type SerializeTupleVariant = ser::Impossible<Vec<String>, Error>;
+    type SerializeMap = ser::Impossible<Vec<String>, Error>;
+    type SerializeStruct = ser::Impossible<Vec<String>, Error>;
+    type SerializeStructVariant = ser::Impossible<Vec<String>, Error>;
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Vec::new())
+    }
+
+    fn serialize_some<T: ?Sized + ser::Serialize>(
+        self,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(ExtraLinesSeq(Vec::new()))
+    }
+
+    fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_i16(self, _v: i16) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_i32(self, _v: i32) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_u8(self, _v: u8) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_u16(self, _v: u16) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_u32(self, _v: u32) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_u64(self, _v: u64) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_str(self, _v: &str) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Vec::new())
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(Vec::new())
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_newtype_struct<T: ?Sized + ser::Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + ser::Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(Error::custom("extra_lines must be a sequence of strings"))
+    }
+}
+
+struct ExtraLinesSeq(Vec<String>);
+
+impl ser::SerializeSeq for ExtraLinesSeq {
+    type Ok = Vec<String>;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + ser::Serialize>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.0.push(value.serialize(ScalarSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.0)
+    }
+}
+
+pub struct Deserializer<'de> {
+    source: &'de str,
+    options: Options<'de>,
+}
+
+impl<'de> Deserializer<'de> {
+    pub fn new(source: &'de str, options: Options<'de>) -> Self {
+        Self { source, options }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(Error::custom("top-level value must be a struct with known field names"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let keys: Vec<&str> = fields
+            .iter()
+            .copied()
+            .filter(|&field| field != "extra_lines")
+            .collect();
+
+        let data = crate::deserializer()
+            .separator(self.options.separator)
+            .newline(self.options.newline)
+            .keys(&keys)
+            .deserialize(self.source);
+
+        visitor.visit_map(StructMapAccess {
+            fields,
+            data,
+            field_idx: 0,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq
+        tuple tuple_struct map enum identifier ignored_any
+    }
+}
+
+struct StructMapAccess<'de> {
+    fields: &'static [&'static str],
+    data: DeserializeData<'de>,
+    field_idx: usize,
+}
+
+impl<'de> de::MapAccess<'de> for StructMapAccess<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        while self.field_idx < self.fields.len() {
+            let field = self.fields[self.field_idx];
+            self.field_idx += 1;
+
+            let present = field == "extra_lines" || self.data.pairs.iter().any(|(k, _)| *k == field);
+            if present {
+                return seed
+                    .deserialize(de::value::StrDeserializer::new(field))
+                    .map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let field = self.fields[self.field_idx - 1];
+
+        if field == "extra_lines" {
+            seed.deserialize(ExtraLinesDeserializer {
+                lines: &self.data.extra_lines,
+            })
+        } else {
+            let value = self
+                .data
+                .pairs
+                .iter()
+                .rev()
+                .find(|(k, _)| *k == field)
+                .map(|(_, v)| *v)
+                .unwrap_or_default();
+            seed.deserialize(ScalarDeserializer { value })
+        }
+    }
+}
+
+struct ScalarDeserializer<'de> {
+    value: &'de str,
+}
+
+impl<'de> de::Deserializer<'de> for ScalarDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_borrowed_str(self.value)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.value.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_bool(
+            self.value
+                .parse()
+                .map_err(|e| Error::custom(format!("invalid bool `{}`: {e}", self.value)))?,
+        )
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_i8(
+            self.value
+                .parse()
+                .map_err(|e| Error::custom(format!("invalid i8 `{}`: {e}", self.value)))?,
+        )
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_i16(
+            self.value
+                .parse()
+                .map_err(|e| Error::custom(format!("invalid i16 `{}`: {e}", self.value)))?,
+        )
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_i32(
+            self.value
+                .parse()
+                .map_err(|e| Error::custom(format!("invalid i32 `{}`: {e}", self.value)))?,
+        )
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_i64(
+            self.value
+                .parse()
+                .map_err(|e| Error::custom(format!("invalid i64 `{}`: {e}", self.value)))?,
+        )
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u8(
+            self.value
+                .parse()
+                .map_err(|e| Error::custom(format!("invalid u8 `{}`: {e}", self.value)))?,
+        )
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u16(
+            self.value
+                .parse()
+                .map_err(|e| Error::custom(format!("invalid u16 `{}`: {e}", self.value)))?,
+        )
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u32(
+            self.value
+                .parse()
+                .map_err(|e| Error::custom(format!("invalid u32 `{}`: {e}", self.value)))?,
+        )
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u64(
+            self.value
+                .parse()
+                .map_err(|e| Error::custom(format!("invalid u64 `{}`: {e}", self.value)))?,
+        )
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_f32(
+            self.value
+                .parse()
+                .map_err(|e| Error::custom(format!("invalid f32 `{}`: {e}", self.value)))?,
+        )
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_f64(
+            self.value
+                .parse()
+                .map_err(|e| Error::custom(format!("invalid f64 `{}`: {e}", self.value)))?,
+        )
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let mut chars = self.value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => visitor.visit_char(c),
+            _ => Err(Error::custom(format!(
+                "expected a single character, got `{}`",
+                self.value
+            ))),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_borrowed_str(self.value)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_borrowed_str(self.value)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct ExtraLinesDeserializer<'a, 'de> {
+    lines: &'a [&'de str],
+}
+
+impl<'a, 'de> de::Deserializer<'de> for ExtraLinesDeserializer<'a, 'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(ExtraLinesSeqAccess {
+            lines: self.lines,
+            idx: 0,
+        })
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct ExtraLinesSeqAccess<'a, 'de> {
+    lines: &'a [&'de str],
+    idx: usize,
+}
+
+impl<'a, 'de> de::SeqAccess<'de> for ExtraLinesSeqAccess<'a, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        if self.idx < self.lines.len() {
+            let value = self.lines[self.idx];
+            self.idx += 1;
+            seed.deserialize(ScalarDeserializer { value }).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Config {
+        name: String,
+        port: u16,
+        verbose: bool,
+        extra_lines: Vec<String>,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let config = Config {
+            name: "svc".to_owned(),
+            port: 8080,
+            verbose: true,
+            extra_lines: vec!["# a comment".to_owned()],
+        };
+
+        let text = to_string(&config).unwrap();
+        let parsed: Config = from_str(&text).unwrap();
+
+        assert_eq!(parsed, config);
+    }
+
+    #[test]
+    fn test_to_string() {
+        let config = Config {
+            name: "svc".to_owned(),
+            port: 8080,
+            verbose: true,
+            extra_lines: vec![],
+        };
+
+        let expected = "\
+            name: svc\n\
+            port: 8080\n\
+            verbose: true\n\
+        ";
+
+        assert_eq!(to_string(&config).unwrap(), expected);
+    }
+}